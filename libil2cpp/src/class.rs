@@ -1,12 +1,20 @@
+use std::any::TypeId;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::ffi::{CStr, CString};
 use std::{fmt, ptr, slice};
 
+use crate::signature::{self, SimpleKey};
 use crate::{
-    raw, Arguments, CalleeReturn, CalleeThis, FieldInfo, Il2CppException, Il2CppType, MethodInfo,
-    Parameters, Return, WrapRaw,
+    method_cache, raw, Arguments, CalleeReturn, CalleeThis, FieldInfo, Il2CppException,
+    Il2CppType, MethodInfo, Parameters, Return, Type, WrapRaw,
 };
 
+/// Marker type distinguishing [`Il2CppClass::find_method_static`]'s cache
+/// entries from [`Il2CppClass::find_method`]'s, even when both are queried
+/// with the same `A`/`R` for the same name.
+struct StaticMethodMarker;
+
 /// An il2cpp class
 #[repr(transparent)]
 pub struct Il2CppClass(raw::Il2CppClass);
@@ -42,95 +50,205 @@ impl Il2CppClass {
         None
     }
 
-    /// Find a method belonging to the class or its parents by name with type
-    /// checking
-    pub fn find_method<A, R, const N: usize>(&self, name: &str) -> Option<&MethodInfo>
-    where
-        A: Arguments<N>,
-        R: Return,
-    {
-        for c in self.hierarchy() {
-            let mut matching = c
-                .methods()
-                .iter()
-                .filter(|mi| {
-                    mi.name() == name && A::matches(mi.parameters()) && R::matches(mi.return_ty())
-                })
-                .copied();
+    /// Collects every method named `name` across the class hierarchy,
+    /// regardless of arity or parameter/return types, grouped by hierarchy
+    /// level (the class itself first, then its parent, and so on).
+    ///
+    /// Use [`MethodCandidates::select`] to apply type checking and pick a
+    /// winner, with a diagnosable [`ResolutionError`] on failure. Levels are
+    /// tried in order and a match at a more-derived level shadows any
+    /// same-signature candidate further up the hierarchy, the same way an
+    /// override does.
+    pub fn candidates(&self, name: &str) -> MethodCandidates<'_> {
+        let levels = self
+            .hierarchy()
+            .map(|c| {
+                c.methods()
+                    .iter()
+                    .copied()
+                    .filter(|mi| mi.name() == name)
+                    .collect()
+            })
+            .collect();
 
-            match match matching.next() {
-                // If we have no matches, we continue to the parent
-                None => continue,
-                Some(mi) => (mi, matching.next()),
-            } {
-                // If we have one match, we return it
-                (mi, None) => return Some(mi),
-                // If we have 2+ matches, we return None to avoid conflicts
-                _ => return None,
+        MethodCandidates::new(levels)
+    }
+
+    /// Collects every method named `name` declared on `iface` itself, or on
+    /// any interface it extends (recursing, since interfaces extend
+    /// interfaces), skipping any method pointer already in `seen`.
+    ///
+    /// Diamond-shaped interface graphs (e.g. `ICollection<T>` and
+    /// `IEnumerable<T>` both ultimately extending `IEnumerable`) mean the
+    /// same method can be reached through more than one path; `seen` is how
+    /// we make sure it only ends up in `out` once.
+    fn interface_method_candidates<'a>(
+        iface: &'a Il2CppClass,
+        name: &str,
+        seen: &mut HashSet<*const MethodInfo>,
+        out: &mut Vec<&'a MethodInfo>,
+    ) {
+        for mi in iface.methods().iter().copied().filter(|mi| mi.name() == name) {
+            if seen.insert(mi as *const MethodInfo) {
+                out.push(mi);
             }
         }
 
-        None
+        for parent in iface.implemented_interfaces() {
+            Self::interface_method_candidates(parent, name, seen, out);
+        }
     }
 
-    /// Find a static method belonging to the class by name with type checking
-    pub fn find_method_static<A, R, const N: usize>(&self, name: &str) -> Option<&MethodInfo>
+    /// Collects every method named `name` declared on any interface
+    /// implemented by a class in the hierarchy (the class itself or one of
+    /// its parents), recursing into interfaces those interfaces extend
+    fn interface_candidates(&self, name: &str) -> MethodCandidates<'_> {
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for c in self.hierarchy() {
+            for iface in c.implemented_interfaces() {
+                Self::interface_method_candidates(iface, name, &mut seen, &mut candidates);
+            }
+        }
+
+        MethodCandidates::flat(candidates)
+    }
+
+    /// Find a method declared directly on `iface` (or an interface it
+    /// extends) with type checking, for resolving default/explicit interface
+    /// implementations by name
+    pub fn find_interface_method<A, R, const N: usize>(
+        &self,
+        iface: &Il2CppClass,
+        name: &str,
+    ) -> Option<&MethodInfo>
     where
         A: Arguments<N>,
+        A::Type: signature::TupleKeys,
         R: Return,
     {
-        let mut matching = self
-            .methods()
-            .iter()
-            .filter(|mi| {
-                mi.name() == name
-                    && mi.is_static()
-                    && A::matches(mi.parameters())
-                    && R::matches(mi.return_ty())
-            })
-            .copied();
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        Self::interface_method_candidates(iface, name, &mut seen, &mut candidates);
+        MethodCandidates::flat(candidates).select::<A, R, N>().ok()
+    }
 
-        match (matching.next(), matching.next()) {
-            // If we have one match, we return it
-            (Some(mi), None) | (None, Some(mi)) => Some(mi),
-            // If we have 2+ or zero matches, we return None
-            _ => None,
+    /// Find a method belonging to the class, its parents, or any interfaces
+    /// they implement, with type checking
+    ///
+    /// Class-declared methods take priority: if the class hierarchy has a
+    /// unique match, it's returned without even considering interfaces: an
+    /// ambiguity among class-declared candidates is reported the same way,
+    /// without falling back to interfaces. Interfaces are only consulted
+    /// when the class hierarchy has no match at all.
+    pub fn find_method_with_interfaces<A, R, const N: usize>(&self, name: &str) -> Option<&MethodInfo>
+    where
+        A: Arguments<N>,
+        A::Type: signature::TupleKeys,
+        R: Return,
+    {
+        match self.candidates(name).select::<A, R, N>() {
+            Ok(mi) => Some(mi),
+            Err(ResolutionError::Ambiguous(_)) => None,
+            Err(_) => self.interface_candidates(name).select::<A, R, N>().ok(),
         }
     }
 
     /// Find a method belonging to the class or its parents by name with type
-    /// checking from a callee perspective
-    pub fn find_method_callee<T, P, R, const N: usize>(&self, name: &str) -> Option<&MethodInfo>
+    /// checking
+    ///
+    /// The result is cached for the lifetime of the process, keyed on the
+    /// class, `name` and the normalized `A`/`R` signature, so `name` must be
+    /// `'static` (in practice always a string literal).
+    pub fn find_method<A, R, const N: usize>(&self, name: &'static str) -> Option<&MethodInfo>
     where
-        T: CalleeThis,
-        P: Parameters<N>,
-        R: CalleeReturn,
+        A: Arguments<N>,
+        A::Type: signature::TupleKeys,
+        R: Return,
     {
-        for c in self.hierarchy() {
-            let mut matching = c
+        let sig = TypeId::of::<(A::Type, R::Type)>();
+        method_cache::get_or_resolve(self, name, sig, || {
+            self.candidates(name)
+                .select::<A, R, N>()
+                .map_err(|err| matches!(err, ResolutionError::Ambiguous(_)))
+        })
+    }
+
+    /// Find a static method belonging to the class by name with type checking
+    ///
+    /// The result is cached for the lifetime of the process; see
+    /// [`find_method`](Self::find_method) for why `name` must be `'static`.
+    pub fn find_method_static<A, R, const N: usize>(&self, name: &'static str) -> Option<&MethodInfo>
+    where
+        A: Arguments<N>,
+        R: Return,
+    {
+        let signature = TypeId::of::<(StaticMethodMarker, A::Type, R::Type)>();
+        method_cache::get_or_resolve(self, name, signature, || {
+            let mut matching = self
                 .methods()
                 .iter()
                 .filter(|mi| {
                     mi.name() == name
-                        && T::matches(mi)
-                        && P::matches(mi.parameters())
+                        && mi.is_static()
+                        && A::matches(mi.parameters())
                         && R::matches(mi.return_ty())
                 })
                 .copied();
 
-            match match matching.next() {
-                // If we have no matches, we continue to the parent
-                None => continue,
-                Some(mi) => (mi, matching.next()),
-            } {
+            match (matching.next(), matching.next()) {
                 // If we have one match, we return it
-                (mi, None) => return Some(mi),
-                // If we have 2+ matches, we return None to avoid conflicts
-                _ => return None,
+                (Some(mi), None) => Ok(mi),
+                // If we have zero matches, we report that
+                (None, None) => Err(false),
+                // If we have 2+ matches, we report ambiguity
+                _ => Err(true),
             }
-        }
+        })
+    }
 
-        None
+    /// Find a method belonging to the class or its parents by name with type
+    /// checking from a callee perspective
+    ///
+    /// The result is cached for the lifetime of the process; see
+    /// [`find_method`](Self::find_method) for why `name` must be `'static`.
+    pub fn find_method_callee<T, P, R, const N: usize>(
+        &self,
+        name: &'static str,
+    ) -> Option<&MethodInfo>
+    where
+        T: CalleeThis,
+        P: Parameters<N>,
+        R: CalleeReturn,
+    {
+        let signature = TypeId::of::<(T::Type, P::Type, R::Type)>();
+        method_cache::get_or_resolve(self, name, signature, || {
+            for c in self.hierarchy() {
+                let mut matching = c
+                    .methods()
+                    .iter()
+                    .filter(|mi| {
+                        mi.name() == name
+                            && T::matches(mi)
+                            && P::matches(mi.parameters())
+                            && R::matches(mi.return_ty())
+                    })
+                    .copied();
+
+                match match matching.next() {
+                    // If we have no matches, we continue to the parent
+                    None => continue,
+                    Some(mi) => (mi, matching.next()),
+                } {
+                    // If we have one match, we return it
+                    (mi, None) => return Ok(mi),
+                    // If we have 2+ matches, we report ambiguity
+                    _ => return Err(true),
+                }
+            }
+
+            Err(false)
+        })
     }
 
     /// Find a method belonging to the class or its parents by name and
@@ -176,9 +294,39 @@ impl Il2CppClass {
         None
     }
 
+    /// Find a field belonging to the class or its parents by name with type
+    /// checking, so that two same-named fields of different types in a
+    /// parent/child don't silently collide
+    pub fn find_field<T: Type>(&self, name: &str) -> Option<&FieldInfo> {
+        for c in self.hierarchy() {
+            let mut matching = c
+                .fields()
+                .iter()
+                .filter(|fi| fi.name() == name && T::matches(fi.ty()))
+                .copied();
+
+            match match matching.next() {
+                // If we have no matches, we continue to the parent
+                None => continue,
+                Some(fi) => (fi, matching.next()),
+            } {
+                // If we have one match, we return it
+                (fi, None) => return Some(fi),
+                // If we have 2+ matches, we return None to avoid conflicts
+                _ => return None,
+            }
+        }
+
+        None
+    }
+
     /// Invokes the static method with the given name using the given arguments,
     /// with type checking
-    pub fn invoke<A, R, const N: usize>(&self, name: &str, args: A) -> Result<R, &Il2CppException>
+    pub fn invoke<A, R, const N: usize>(
+        &self,
+        name: &'static str,
+        args: A,
+    ) -> Result<R, &Il2CppException>
     where
         A: Arguments<N>,
         R: Return,
@@ -273,6 +421,87 @@ pub struct Hierarchy<'a> {
     current: Option<&'a Il2CppClass>,
 }
 
+/// Methods sharing a name, collected across a class hierarchy by
+/// [`Il2CppClass::candidates`], grouped by hierarchy level, before overload
+/// resolution picks a winner
+pub struct MethodCandidates<'a> {
+    levels: Vec<Vec<&'a MethodInfo>>,
+}
+
+impl<'a> MethodCandidates<'a> {
+    fn new(levels: Vec<Vec<&'a MethodInfo>>) -> Self {
+        Self { levels }
+    }
+
+    /// Wraps a single, already-flat list of candidates as one level, for
+    /// sources (like interface lookups) that have no hierarchy levels of
+    /// their own to preserve precedence between.
+    fn flat(candidates: Vec<&'a MethodInfo>) -> Self {
+        Self::new(vec![candidates])
+    }
+
+    /// Applies type checking and returns the unique match, or a
+    /// [`ResolutionError`] describing why none could be picked.
+    ///
+    /// Levels are tried in order: the first level with any type-matching
+    /// candidate wins (an ambiguity there is reported immediately, without
+    /// falling through to a less-derived level), mirroring how an override
+    /// shadows the method it overrides.
+    pub fn select<A, R, const N: usize>(&self) -> Result<&'a MethodInfo, ResolutionError<'a>>
+    where
+        A: Arguments<N>,
+        A::Type: signature::TupleKeys,
+        R: Return,
+    {
+        // Cheaply rule out candidates whose arity, per-parameter types or
+        // return type can't possibly match before paying for the full
+        // `A`/`R` type check.
+        let want_params = <A::Type as signature::TupleKeys>::simple_keys();
+        let want_return = SimpleKey::of_query::<R::Type>();
+        let mut any_candidates = false;
+
+        for level in &self.levels {
+            any_candidates |= !level.is_empty();
+
+            let mut matching = level.iter().copied().filter(|mi| {
+                !signature::fast_reject(*mi, &want_params, want_return)
+                    && A::matches(mi.parameters())
+                    && R::matches(mi.return_ty())
+            });
+
+            match (matching.next(), matching.next()) {
+                // No match at this level: fall through to the parent
+                (None, None) => continue,
+                (Some(mi), None) => return Ok(mi),
+                (Some(first), Some(second)) => {
+                    let mut ambiguous = vec![first, second];
+                    ambiguous.extend(matching);
+                    return Err(ResolutionError::Ambiguous(ambiguous));
+                }
+                (None, Some(_)) => unreachable!(),
+            }
+        }
+
+        if !any_candidates {
+            Err(ResolutionError::NotFound)
+        } else {
+            let mismatched = self.levels.iter().flatten().copied().collect();
+            Err(ResolutionError::SignatureMismatch(mismatched))
+        }
+    }
+}
+
+/// Why [`MethodCandidates::select`] failed to produce a unique [`MethodInfo`]
+pub enum ResolutionError<'a> {
+    /// No method with the queried name exists anywhere in the hierarchy
+    NotFound,
+    /// More than one same-named method matched the queried signature
+    Ambiguous(Vec<&'a MethodInfo>),
+    /// Methods with the queried name exist, but none matched the queried
+    /// parameter/return types
+    SignatureMismatch(Vec<&'a MethodInfo>),
+}
+
 unsafe impl WrapRaw for Il2CppClass {
     type Raw = raw::Il2CppClass;
 }
@@ -5,6 +5,11 @@ use std::mem::transmute;
 use super::{Argument, Il2CppClass, Il2CppObject, Il2CppType, Return, WrapRaw};
 use crate::raw;
 
+/// `FieldAttributes.Static`, see ECMA-335 II.23.1.5
+const FIELD_ATTRIBUTE_STATIC: u16 = 0x0010;
+/// `FieldAttributes.Literal`, see ECMA-335 II.23.1.5
+const FIELD_ATTRIBUTE_LITERAL: u16 = 0x0040;
+
 /// Information about a C# field
 #[repr(transparent)]
 pub struct FieldInfo(raw::FieldInfo);
@@ -72,6 +77,26 @@ impl FieldInfo {
     pub fn ty(&self) -> &Il2CppType {
         unsafe { Il2CppType::wrap_ptr(self.raw().type_) }.unwrap()
     }
+
+    /// Offset of the field within its declaring type's instance layout
+    ///
+    /// Static fields are routed through the class's static storage instead,
+    /// and literal fields have no storage at all, so neither has a
+    /// meaningful instance offset; check [`is_static`](Self::is_static) and
+    /// [`is_literal`](Self::is_literal) first.
+    pub fn offset(&self) -> i32 {
+        self.raw().offset
+    }
+
+    /// Whether the field is `static`
+    pub fn is_static(&self) -> bool {
+        self.ty().attrs() & FIELD_ATTRIBUTE_STATIC != 0
+    }
+
+    /// Whether the field is a compile-time constant (`const`)
+    pub fn is_literal(&self) -> bool {
+        self.ty().attrs() & FIELD_ATTRIBUTE_LITERAL != 0
+    }
 }
 
 unsafe impl WrapRaw for FieldInfo {
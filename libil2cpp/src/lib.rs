@@ -7,16 +7,18 @@ mod array;
 mod class;
 mod exception;
 mod field_info;
+mod method_cache;
 mod method_info;
 mod object;
 mod parameter_info;
 pub mod raw;
+mod signature;
 mod string;
 mod ty;
 mod typecheck;
 
 pub use array::Il2CppArray;
-pub use class::Il2CppClass;
+pub use class::{Il2CppClass, MethodCandidates, ResolutionError};
 pub use exception::Il2CppException;
 pub use field_info::FieldInfo;
 pub use method_info::MethodInfo;
@@ -0,0 +1,63 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::lazy::SyncLazy;
+use std::sync::Mutex;
+
+use crate::{Il2CppClass, MethodInfo};
+
+/// Outcome of a previous resolution attempt against `(class, name,
+/// signature)`. Caching failures as well as successes means a game update
+/// that removes an overload doesn't turn every subsequent lookup back into a
+/// full hierarchy walk.
+enum Resolution {
+    Unique(*const MethodInfo),
+    Ambiguous,
+    NotFound,
+}
+
+// SAFETY: these are il2cpp method/class pointers, which are valid and
+// stable for the lifetime of the process, so sharing them across threads is
+// sound.
+unsafe impl Send for Resolution {}
+unsafe impl Sync for Resolution {}
+
+type Key = (*const Il2CppClass, &'static str, TypeId);
+
+static CACHE: SyncLazy<Mutex<HashMap<Key, Resolution>>> = SyncLazy::new(Default::default);
+
+/// Resolves `(class, name, signature)` against the global method cache,
+/// falling back to `resolve` on a miss and remembering the outcome for next
+/// time.
+///
+/// `signature` should be built from `TypeId::of` on the normalized argument
+/// and return types being matched (their `Type` associated types), so that
+/// different queries for the same name never collide. `resolve` returns
+/// `Err(true)` for an ambiguous match and `Err(false)` for no match.
+pub(crate) fn get_or_resolve<'a>(
+    class: &'a Il2CppClass,
+    name: &'static str,
+    signature: TypeId,
+    resolve: impl FnOnce() -> Result<&'a MethodInfo, bool>,
+) -> Option<&'a MethodInfo> {
+    let key = (class as *const Il2CppClass, name, signature);
+
+    if let Some(cached) = CACHE.lock().unwrap().get(&key) {
+        return match cached {
+            Resolution::Unique(mi) => Some(unsafe { &**mi }),
+            Resolution::Ambiguous | Resolution::NotFound => None,
+        };
+    }
+
+    let resolution = match resolve() {
+        Ok(mi) => Resolution::Unique(mi as *const MethodInfo),
+        Err(true) => Resolution::Ambiguous,
+        Err(false) => Resolution::NotFound,
+    };
+    let result = match resolution {
+        Resolution::Unique(mi) => Some(unsafe { &*mi }),
+        _ => None,
+    };
+
+    CACHE.lock().unwrap().insert(key, resolution);
+    result
+}
@@ -0,0 +1,147 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::lazy::SyncLazy;
+use std::sync::Mutex;
+
+use crate::{Builtin, Il2CppType, MethodInfo};
+
+/// Coarse shape of a type, cheap enough to compare before paying for a full
+/// `A::matches`/`R::matches` check.
+///
+/// [`SimpleKey::Wildcard`] is the safe default: it never causes a candidate
+/// to be rejected, so an ambiguous simplification (a generic parameter, or a
+/// `Type` we have no special-cased mapping for) can only ever fail to save
+/// work, never change which method ends up being picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SimpleKey {
+    Void,
+    I4,
+    R8,
+    Object,
+    ValueType,
+    Ptr,
+    Wildcard,
+}
+
+impl SimpleKey {
+    /// Classifies a runtime [`Il2CppType`], e.g. a candidate's actual
+    /// parameter or return type.
+    fn of_runtime(ty: &Il2CppType) -> Self {
+        if ty.is_builtin(Builtin::Void) {
+            SimpleKey::Void
+        } else if ty.is_builtin(Builtin::I4) {
+            SimpleKey::I4
+        } else if ty.is_builtin(Builtin::R8) {
+            SimpleKey::R8
+        } else if ty.is_builtin(Builtin::Object) {
+            SimpleKey::Object
+        } else if ty.is_builtin(Builtin::ValueType) {
+            SimpleKey::ValueType
+        } else if ty.is_builtin(Builtin::Ptr) {
+            SimpleKey::Ptr
+        } else {
+            SimpleKey::Wildcard
+        }
+    }
+
+    /// Classifies a query's normalized `Type` by the handful of concrete
+    /// Rust types we can recognize by [`TypeId`]. Anything else (a
+    /// user-defined wrapper type, a generic) maps to [`SimpleKey::Wildcard`].
+    pub(crate) fn of_query<T: Any>() -> Self {
+        let id = TypeId::of::<T>();
+        if id == TypeId::of::<()>() {
+            SimpleKey::Void
+        } else if id == TypeId::of::<i32>() {
+            SimpleKey::I4
+        } else if id == TypeId::of::<f64>() {
+            SimpleKey::R8
+        } else {
+            SimpleKey::Wildcard
+        }
+    }
+
+    /// Whether a candidate with this key could possibly satisfy a query
+    /// expecting `want`. Either side being [`SimpleKey::Wildcard`] always
+    /// accepts, so this can only ever rule out a definite mismatch.
+    fn accepts(self, want: Self) -> bool {
+        self == SimpleKey::Wildcard || want == SimpleKey::Wildcard || self == want
+    }
+}
+
+/// Maps a query's normalized argument tuple `Type` (e.g. `Arguments::Type`,
+/// which is `()` for zero parameters or `(P1::Type, P2::Type, ...)`
+/// otherwise, mirroring the callee-side `Parameters::Type` convention) to
+/// its per-position [`SimpleKey`]s, for the fast-reject pre-filter.
+pub(crate) trait TupleKeys {
+    fn simple_keys() -> Vec<SimpleKey>;
+}
+
+macro_rules! impl_tuple_keys {
+    ($($t:ident),*) => {
+        impl<$($t: Any),*> TupleKeys for ($($t,)*) {
+            fn simple_keys() -> Vec<SimpleKey> {
+                vec![$(SimpleKey::of_query::<$t>()),*]
+            }
+        }
+    };
+}
+
+impl_tuple_keys!();
+impl_tuple_keys!(A);
+impl_tuple_keys!(A, B);
+impl_tuple_keys!(A, B, C);
+impl_tuple_keys!(A, B, C, D);
+impl_tuple_keys!(A, B, C, D, E);
+impl_tuple_keys!(A, B, C, D, E, F);
+impl_tuple_keys!(A, B, C, D, E, F, G);
+impl_tuple_keys!(A, B, C, D, E, F, G, H);
+
+/// A method's simplified parameter/return signature, computed once and
+/// cached for the lifetime of the process.
+struct Signature {
+    parameters: Box<[SimpleKey]>,
+    return_ty: SimpleKey,
+}
+
+static SIGNATURES: SyncLazy<Mutex<HashMap<*const MethodInfo, &'static Signature>>> =
+    SyncLazy::new(Default::default);
+
+fn signature_of(mi: &MethodInfo) -> &'static Signature {
+    let key = mi as *const MethodInfo;
+
+    if let Some(sig) = SIGNATURES.lock().unwrap().get(&key) {
+        return sig;
+    }
+
+    let signature = Box::leak(Box::new(Signature {
+        parameters: mi
+            .parameters()
+            .iter()
+            .map(|p| SimpleKey::of_runtime(p.ty()))
+            .collect(),
+        return_ty: SimpleKey::of_runtime(mi.return_ty()),
+    }));
+
+    SIGNATURES.lock().unwrap().insert(key, signature);
+    signature
+}
+
+/// Cheaply rules out `mi` as a candidate for a query expecting `want_params`
+/// (one [`SimpleKey`] per parameter, in order) and a return type classified
+/// as `want_return`, without running the (potentially expensive)
+/// `A::matches`/`R::matches` checks.
+///
+/// Never rejects a candidate that could actually match: the arity check is
+/// exact, and each per-position/return comparison only rejects a definite
+/// mismatch between two non-wildcard keys.
+pub(crate) fn fast_reject(mi: &MethodInfo, want_params: &[SimpleKey], want_return: SimpleKey) -> bool {
+    let signature = signature_of(mi);
+
+    signature.parameters.len() != want_params.len()
+        || !signature.return_ty.accepts(want_return)
+        || signature
+            .parameters
+            .iter()
+            .zip(want_params)
+            .any(|(&have, &want)| !have.accepts(want))
+}